@@ -15,6 +15,9 @@ const FOOD_COLOR: Color = Color::srgb(1.0, 0.1, 0.0);
 const BACKGROUND_COLOR: Color = Color::srgb(0.04, 0.04, 0.04);
 const SCORE_COLOR: Color = Color::srgb(0.8, 0.8, 0.8);
 const SNAKE_MOVE_SPEED: f32 = 0.075; // Lower number = faster speed (seconds between moves)
+const MIN_SNAKE_MOVE_SPEED: f32 = 0.03; // Fastest the snake is allowed to speed up to
+const FOODS_PER_SPEEDUP: u32 = 5; // Speed up every N foods eaten
+const SPEEDUP_FACTOR: f32 = 0.95; // Multiplier applied to the move speed at each speedup
 const GAME_FONT: &str = "fonts/Rena-BoldDisplay.ttf";
 
 #[derive(Default, States, Clone, Eq, PartialEq, Debug, Hash)]
@@ -28,6 +31,11 @@ enum GameState {
 #[derive(Component)]
 struct SnakeHead {
     direction: Direction,
+    /// Direction requested by the player since the last movement tick.
+    /// Only committed to `direction` once per tick in `snake_movement`, so a
+    /// quick double tap can't reverse the head into its own neck between
+    /// ticks.
+    intention: Direction,
 }
 
 #[derive(Component)]
@@ -39,20 +47,49 @@ struct Food;
 #[derive(Component)]
 struct Wall;
 
+/// Integer grid coordinates. All game logic operates on these rather than
+/// on the `Transform` world-space translation, which is derived from them
+/// each frame by `position_translation`.
+#[derive(Component, Clone, Copy, PartialEq, Eq, Debug)]
+struct Position {
+    x: i32,
+    y: i32,
+}
+
+/// Size of an entity in grid cells. `size_scaling` turns this into the
+/// sprite's pixel `custom_size` based on the current window size, so the
+/// game rescales cleanly if the window or `GRID_SIZE` changes.
+#[derive(Component)]
+struct GridSize {
+    width: f32,
+    height: f32,
+}
+
+impl GridSize {
+    fn square(x: f32) -> Self {
+        Self {
+            width: x,
+            height: x,
+        }
+    }
+}
+
 #[derive(Resource, Default, Deref, DerefMut)]
 struct SnakeSegments(Vec<Entity>);
 
 #[derive(Resource, Default)]
 struct Score(u32);
 
-#[derive(Resource)]
-struct MovementTimer(Timer);
-
 #[derive(Event)]
 struct GrowthEvent;
 
 #[derive(Event)]
-struct GameOverEvent;
+enum GameOverEvent {
+    /// The snake hit a wall or itself.
+    Loss,
+    /// Every grid cell is filled with the snake, so no more food can spawn.
+    Win,
+}
 
 #[derive(PartialEq, Copy, Clone)]
 enum Direction {
@@ -73,6 +110,17 @@ impl Direction {
     }
 }
 
+/// Stages of a single snake simulation step, ordered explicitly below
+/// rather than via one opaque `.chain()`, so new gameplay systems (e.g. a
+/// power-up) can be slotted in `before`/`after` the stage they affect.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SnakeMovement {
+    Input,
+    Movement,
+    Eating,
+    Growth,
+}
+
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins.set(WindowPlugin {
@@ -87,26 +135,48 @@ fn main() {
         .insert_resource(ClearColor(BACKGROUND_COLOR))
         .insert_resource(SnakeSegments::default())
         .insert_resource(Score::default())
-        .insert_resource(MovementTimer(Timer::from_seconds(
-            SNAKE_MOVE_SPEED,
-            TimerMode::Repeating,
-        )))
+        .insert_resource(Time::<Fixed>::from_seconds(SNAKE_MOVE_SPEED as f64))
         .add_event::<GrowthEvent>()
         .add_event::<GameOverEvent>()
-        .add_systems(Startup, setup.run_if(|windows: Query<&Window>| windows.get_single().is_ok()))
+        .add_systems(
+            Startup,
+            setup.run_if(|windows: Query<&Window>| windows.get_single().is_ok()),
+        )
+        .configure_sets(
+            Update,
+            SnakeMovement::Input.run_if(in_state(GameState::Playing)),
+        )
+        .configure_sets(
+            FixedUpdate,
+            (
+                SnakeMovement::Movement,
+                SnakeMovement::Eating.after(SnakeMovement::Movement),
+                SnakeMovement::Growth.after(SnakeMovement::Eating),
+            )
+                .run_if(in_state(GameState::Playing)),
+        )
         .add_systems(
             Update,
             (
                 handle_pause,
-                snake_movement_input.run_if(in_state(GameState::Playing)),
-                snake_movement.run_if(in_state(GameState::Playing)),
-                snake_eating.run_if(in_state(GameState::Playing)),
-                snake_growth.run_if(in_state(GameState::Playing)),
-                game_over.run_if(in_state(GameState::Playing)),
+                snake_movement_input.in_set(SnakeMovement::Input),
+                size_scaling,
+                position_translation,
                 update_scoreboard,
+                update_difficulty,
                 handle_game_over.run_if(in_state(GameState::GameOver)),
-            )
-                .chain(),
+            ),
+        )
+        .add_systems(
+            FixedUpdate,
+            (
+                snake_movement.in_set(SnakeMovement::Movement),
+                snake_eating.in_set(SnakeMovement::Eating),
+                snake_growth.in_set(SnakeMovement::Growth),
+                game_over
+                    .after(SnakeMovement::Eating)
+                    .run_if(in_state(GameState::Playing)),
+            ),
         )
         .run();
 }
@@ -115,19 +185,26 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>, windows: Query<
     // Camera
     commands.spawn(Camera2d);
 
+    let grid_center = (GRID_SIZE / 2) as i32;
+
     // First spawn the head
     let head = commands
         .spawn((
             Sprite {
                 color: SNAKE_HEAD_COLOR,
-                custom_size: Some(Vec2::new(CELL_SIZE, CELL_SIZE)),
                 ..default()
             },
-            Transform::from_xyz(0.0, 0.0, 0.0),
+            Transform::default(),
             Visibility::default(),
             SnakeHead {
                 direction: Direction::Up,
+                intention: Direction::Up,
+            },
+            Position {
+                x: grid_center,
+                y: grid_center,
             },
+            GridSize::square(1.0),
         ))
         .id();
 
@@ -136,12 +213,16 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>, windows: Query<
         .spawn((
             Sprite {
                 color: SNAKE_SEGMENT_COLOR,
-                custom_size: Some(Vec2::new(CELL_SIZE, CELL_SIZE)),
                 ..default()
             },
-            Transform::from_xyz(0.0, -CELL_SIZE, 0.0),
+            Transform::default(),
             Visibility::default(),
             SnakeSegment,
+            Position {
+                x: grid_center,
+                y: grid_center - 1,
+            },
+            GridSize::square(1.0),
         ))
         .id();
 
@@ -149,8 +230,18 @@ fn setup(mut commands: Commands, asset_server: Res<AssetServer>, windows: Query<
     commands.insert_resource(SnakeSegments(vec![head, segment]));
 
     // Food
-    if let Ok(window) = windows.get_single() {
-        spawn_food(&mut commands, window);
+    if windows.get_single().is_ok() {
+        let occupied = [
+            Position {
+                x: grid_center,
+                y: grid_center,
+            },
+            Position {
+                x: grid_center,
+                y: grid_center - 1,
+            },
+        ];
+        spawn_food(&mut commands, &occupied);
     }
 
     // Scoreboard
@@ -192,10 +283,10 @@ fn snake_movement_input(
         {
             Direction::Right
         } else {
-            head.direction
+            head.intention
         };
         if dir != head.direction.opposite() {
-            head.direction = dir;
+            head.intention = dir;
         }
     }
 }
@@ -203,54 +294,48 @@ fn snake_movement_input(
 fn snake_movement(
     mut game_over_writer: EventWriter<GameOverEvent>,
     segments: ResMut<SnakeSegments>,
-    mut heads: Query<(Entity, &SnakeHead)>,
-    mut positions: Query<&mut Transform>,
-    time: Res<Time>,
-    mut timer: ResMut<MovementTimer>,
-    windows: Query<&Window>,
+    mut heads: Query<(Entity, &mut SnakeHead)>,
+    mut positions: Query<&mut Position>,
 ) {
-    if !timer.0.tick(time.delta()).finished() {
-        return;
-    }
+    if let Some((head_entity, mut head)) = heads.iter_mut().next() {
+        head.direction = head.intention;
 
-    if let Some((head_entity, head)) = heads.iter_mut().next() {
         let segment_positions = segments
             .iter()
             .skip(1)
-            .map(|e| positions.get_mut(*e).unwrap().translation)
-            .collect::<Vec<Vec3>>();
+            .map(|e| *positions.get_mut(*e).unwrap())
+            .collect::<Vec<Position>>();
 
         let mut head_pos = positions.get_mut(head_entity).unwrap();
         match &head.direction {
-            Direction::Left => head_pos.translation.x -= CELL_SIZE,
-            Direction::Right => head_pos.translation.x += CELL_SIZE,
-            Direction::Up => head_pos.translation.y += CELL_SIZE,
-            Direction::Down => head_pos.translation.y -= CELL_SIZE,
+            Direction::Left => head_pos.x -= 1,
+            Direction::Right => head_pos.x += 1,
+            Direction::Up => head_pos.y += 1,
+            Direction::Down => head_pos.y -= 1,
         };
 
         // Check for self-collision
-        if segment_positions.contains(&head_pos.translation) {
-            game_over_writer.send(GameOverEvent);
+        if segment_positions.contains(&head_pos) {
+            game_over_writer.send(GameOverEvent::Loss);
         }
 
-        // Check for window bounds collision
-        let half_size = (GRID_SIZE as f32 / 2.0) * CELL_SIZE;
-        if head_pos.translation.x < -half_size
-            || head_pos.translation.x >= half_size
-            || head_pos.translation.y < -half_size
-            || head_pos.translation.y >= half_size
+        // Check for grid bounds collision
+        if head_pos.x < 0
+            || head_pos.x >= GRID_SIZE as i32
+            || head_pos.y < 0
+            || head_pos.y >= GRID_SIZE as i32
         {
-            game_over_writer.send(GameOverEvent);
+            game_over_writer.send(GameOverEvent::Loss);
         }
 
         // Update body segments
-        let head_pos = positions.get(head_entity).unwrap().translation;
+        let head_pos = *positions.get(head_entity).unwrap();
         for (i, segment) in segments.iter().skip(1).enumerate() {
-            *positions.get_mut(*segment).unwrap() = Transform::from_translation(if i == 0 {
+            *positions.get_mut(*segment).unwrap() = if i == 0 {
                 head_pos
             } else {
                 segment_positions[i - 1]
-            });
+            };
         }
     }
 }
@@ -258,63 +343,89 @@ fn snake_movement(
 fn snake_eating(
     mut commands: Commands,
     mut growth_writer: EventWriter<GrowthEvent>,
+    mut game_over_writer: EventWriter<GameOverEvent>,
     mut score: ResMut<Score>,
-    food_positions: Query<(Entity, &Transform), With<Food>>,
-    head_positions: Query<&Transform, With<SnakeHead>>,
-    windows: Query<&Window>,
+    food_positions: Query<(Entity, &Position), With<Food>>,
+    head_positions: Query<&Position, With<SnakeHead>>,
+    segments: Res<SnakeSegments>,
+    positions: Query<&Position>,
 ) {
     for head_pos in head_positions.iter() {
         for (food_entity, food_pos) in food_positions.iter() {
-            if (head_pos.translation.x - food_pos.translation.x).abs() < CELL_SIZE / 2.0
-                && (head_pos.translation.y - food_pos.translation.y).abs() < CELL_SIZE / 2.0
-            {
+            if head_pos == food_pos {
                 commands.entity(food_entity).despawn();
                 growth_writer.send(GrowthEvent);
                 score.0 += 1;
-                if let Ok(window) = windows.get_single() {
-                    spawn_food(&mut commands, window);
+                let occupied = segments
+                    .iter()
+                    .map(|e| *positions.get(*e).unwrap())
+                    .collect::<Vec<Position>>();
+                if !spawn_food(&mut commands, &occupied) {
+                    game_over_writer.send(GameOverEvent::Win);
                 }
             }
         }
     }
 }
 
-fn spawn_food(commands: &mut Commands, window: &Window) {
+/// Spawns food on a random grid cell, skipping any cell in `occupied`
+/// (the snake's current body). Tries a bounded number of random cells
+/// first, then falls back to an exhaustive scan of the grid for a free
+/// cell rather than looping forever on a nearly-full board. Returns
+/// `false` only once that scan also turns up nothing, i.e. the board is
+/// genuinely full.
+fn spawn_food(commands: &mut Commands, occupied: &[Position]) -> bool {
+    const MAX_ATTEMPTS: u32 = 50;
+
     let mut rng = thread_rng();
-    let half_grid = (GRID_SIZE as f32 / 2.0);
-    
-    // Generate position in grid coordinates
-    let grid_x = rng.gen_range(-half_grid..half_grid);
-    let grid_y = rng.gen_range(-half_grid..half_grid);
-    
-    // Convert to world coordinates and ensure alignment to grid
-    let x = grid_x.floor() * CELL_SIZE;
-    let y = grid_y.floor() * CELL_SIZE;
+    let mut pos = None;
+    for _ in 0..MAX_ATTEMPTS {
+        let candidate = Position {
+            x: rng.gen_range(0..GRID_SIZE as i32),
+            y: rng.gen_range(0..GRID_SIZE as i32),
+        };
+        if !occupied.contains(&candidate) {
+            pos = Some(candidate);
+            break;
+        }
+    }
+
+    let pos = pos.or_else(|| {
+        (0..GRID_SIZE as i32)
+            .flat_map(|x| (0..GRID_SIZE as i32).map(move |y| Position { x, y }))
+            .find(|pos| !occupied.contains(pos))
+    });
+
+    let Some(pos) = pos else {
+        return false;
+    };
 
     commands.spawn((
         Sprite {
             color: FOOD_COLOR,
-            custom_size: Some(Vec2::new(CELL_SIZE, CELL_SIZE)),
             ..default()
         },
-        Transform::from_xyz(x, y, 0.0),
+        Transform::default(),
         Visibility::default(),
         Food,
+        pos,
+        GridSize::square(1.0),
     ));
+    true
 }
 
 fn snake_growth(
     mut commands: Commands,
     mut segments: ResMut<SnakeSegments>,
     mut growth_reader: EventReader<GrowthEvent>,
-    positions: Query<&Transform>,
+    positions: Query<&Position>,
 ) {
     if growth_reader.read().next().is_some() {
         // Get the position of the last segment
         let last_segment_pos = if let Some(last_segment) = segments.last() {
-            positions.get(*last_segment).unwrap().translation
+            *positions.get(*last_segment).unwrap()
         } else {
-            Vec3::ZERO // Fallback, should never happen
+            Position { x: 0, y: 0 } // Fallback, should never happen
         };
 
         segments.push(
@@ -322,12 +433,13 @@ fn snake_growth(
                 .spawn((
                     Sprite {
                         color: SNAKE_SEGMENT_COLOR,
-                        custom_size: Some(Vec2::new(CELL_SIZE, CELL_SIZE)),
                         ..default()
                     },
-                    Transform::from_translation(last_segment_pos),
+                    Transform::default(),
                     Visibility::default(),
                     SnakeSegment,
+                    last_segment_pos,
+                    GridSize::square(1.0),
                 ))
                 .id(),
         );
@@ -339,10 +451,14 @@ fn game_over(
     mut reader: EventReader<GameOverEvent>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
-    if reader.read().next().is_some() {
+    if let Some(event) = reader.read().next() {
         next_state.set(GameState::GameOver);
+        let message = match event {
+            GameOverEvent::Loss => "Game Over! Press SPACE to restart",
+            GameOverEvent::Win => "You filled the board! Press SPACE to restart",
+        };
         commands.spawn((
-            Text::new("Game Over! Press SPACE to restart"),
+            Text::new(message),
             TextFont {
                 font_size: 40.0,
                 ..default()
@@ -401,6 +517,19 @@ fn update_scoreboard(score: Res<Score>, mut query: Query<&mut Text>) {
     }
 }
 
+/// Speeds up the `FixedUpdate` movement step as the score rises, giving a
+/// classic accelerating-snake feel. Recomputed from scratch each time the
+/// score changes so restarting resets the speed.
+fn update_difficulty(score: Res<Score>, mut fixed_time: ResMut<Time<Fixed>>) {
+    if !score.is_changed() {
+        return;
+    }
+
+    let speedups = score.0 / FOODS_PER_SPEEDUP;
+    let step = (SNAKE_MOVE_SPEED * SPEEDUP_FACTOR.powi(speedups as i32)).max(MIN_SNAKE_MOVE_SPEED);
+    fixed_time.set_timestep_seconds(step as f64);
+}
+
 fn handle_pause(
     keyboard_input: Res<ButtonInput<KeyCode>>,
     mut next_state: ResMut<NextState<GameState>>,
@@ -414,3 +543,37 @@ fn handle_pause(
         }
     }
 }
+
+/// Scales each grid entity's sprite to the current cell size, so the game
+/// rescales cleanly if the window size or `GRID_SIZE` changes.
+fn size_scaling(windows: Query<&Window>, mut q: Query<(&GridSize, &mut Sprite)>) {
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    for (size, mut sprite) in q.iter_mut() {
+        sprite.custom_size = Some(Vec2::new(
+            size.width / GRID_SIZE as f32 * window.width(),
+            size.height / GRID_SIZE as f32 * window.height(),
+        ));
+    }
+}
+
+/// Maps each entity's grid `Position` to a window-centered `Transform`
+/// translation.
+fn position_translation(windows: Query<&Window>, mut q: Query<(&Position, &mut Transform)>) {
+    fn convert(pos: f32, bound_window: f32, bound_game: f32) -> f32 {
+        let tile_size = bound_window / bound_game;
+        pos / bound_game * bound_window - (bound_window / 2.0) + (tile_size / 2.0)
+    }
+
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    for (pos, mut transform) in q.iter_mut() {
+        transform.translation = Vec3::new(
+            convert(pos.x as f32, window.width(), GRID_SIZE as f32),
+            convert(pos.y as f32, window.height(), GRID_SIZE as f32),
+            0.0,
+        );
+    }
+}